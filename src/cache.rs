@@ -0,0 +1,73 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Default time-to-live for cached pages before they're considered stale.
+pub const DEFAULT_TTL_HOURS: i64 = 24;
+
+/// On-disk cache of scraped HTML, keyed by the request URL, so repeated
+/// searches and recipe fetches don't have to hit the network every time.
+pub struct PageCache {
+    conn: Connection,
+    ttl_hours: i64,
+}
+
+impl PageCache {
+    pub fn new(ttl_hours: i64) -> Result<Self> {
+        let conn = Connection::open("recipes.db")?;
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS page_cache (
+                url TEXT PRIMARY KEY,
+                body TEXT NOT NULL,
+                fetched_at TEXT NOT NULL
+            )", [])?;
+
+        Ok(PageCache { conn, ttl_hours })
+    }
+
+    /// Returns the cached body for `url` if present and not older than the
+    /// configured TTL.
+    pub fn get(&self, url: &str) -> Result<Option<String>> {
+        let row: Option<(String, String)> = self.conn.query_row(
+            "SELECT body, fetched_at FROM page_cache WHERE url = ?1",
+            params![url],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+
+        let Some((body, fetched_at)) = row else {
+            return Ok(None);
+        };
+
+        let fetched_at: DateTime<Utc> = fetched_at.parse()?;
+        if Utc::now().signed_duration_since(fetched_at) < Duration::hours(self.ttl_hours) {
+            Ok(Some(body))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Stores (or replaces) the cached body for `url`, stamped with the
+    /// current time.
+    pub fn put(&self, url: &str, body: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO page_cache (url, body, fetched_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(url) DO UPDATE SET body = excluded.body, fetched_at = excluded.fetched_at",
+            params![url, body, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(())
+    }
+
+    /// Removes entries older than the configured TTL, returning how many
+    /// were deleted.
+    pub fn clear_stale(&self) -> Result<usize> {
+        let cutoff = Utc::now() - Duration::hours(self.ttl_hours);
+        let deleted = self.conn.execute(
+            "DELETE FROM page_cache WHERE fetched_at < ?1",
+            params![cutoff.to_rfc3339()],
+        )?;
+
+        Ok(deleted)
+    }
+}
@@ -1,22 +1,28 @@
-use dialoguer::{Input, Select, Confirm, MultiSelect};
+use dialoguer::{Input, Select, Confirm, MultiSelect, Editor};
 use colored::*;
 use anyhow::Result;
 
-use crate::htmlscraper::{search_recipes, fetch_recipe_details};
-use crate::database::{RecipeDatabase, Recipe};
+use crate::htmlscraper::{available_sources, source_by_id, SearchResult};
+use crate::database::{RecipeDatabase, Recipe, Lang, RequestOpts};
+use crate::cache::{PageCache, DEFAULT_TTL_HOURS};
 
 pub fn run_cli() -> Result<()> {
     let mut db = RecipeDatabase::new()?;
+    let cache = PageCache::new(DEFAULT_TTL_HOURS)?;
+    let mut display_lang: Option<Lang> = None;
 
     loop {
         println!("\n{}", "Recipe Manager CLI".blue().bold());
         let options = vec![
-            "Search Recipes", 
+            "Search Recipes",
             "View Saved Recipes",
+            "Edit Recipe",
             "Delete Saved Recipes",
-            "View Shopping List", 
+            "View Shopping List",
             "Add to Shopping List",
-            "Mark Ingredients", 
+            "Mark Ingredients",
+            "Clear Stale Cache Entries",
+            "Settings",
             "Exit"
         ];
 
@@ -25,14 +31,19 @@ pub fn run_cli() -> Result<()> {
             .items(&options)
             .interact()?;
 
+        let opts = RequestOpts { lang: display_lang };
+
         match selection {
-            0 => search_and_save_recipe(&db)?,
-            1 => view_saved_recipes(&db)?,
-            2 => delete_saved_recipes(&db)?,
-            3 => view_shopping_list(&db)?,
-            4 => add_to_shopping_list(&db)?,
-            5 => mark_ingredients(&mut db)?,
-            6 => break,
+            0 => search_and_save_recipe(&db, &cache)?,
+            1 => view_saved_recipes(&db, opts)?,
+            2 => edit_recipe(&db)?,
+            3 => delete_saved_recipes(&db)?,
+            4 => view_shopping_list(&db, opts)?,
+            5 => add_to_shopping_list(&db, opts)?,
+            6 => mark_ingredients(&mut db, opts)?,
+            7 => clear_stale_cache(&cache)?,
+            8 => settings_menu(&db, &mut display_lang)?,
+            9 => break,
             _ => unreachable!(),
         }
     }
@@ -40,7 +51,92 @@ pub fn run_cli() -> Result<()> {
     Ok(())
 }
 
-fn add_to_shopping_list(db: &RecipeDatabase) -> Result<()> {
+fn settings_menu(db: &RecipeDatabase, display_lang: &mut Option<Lang>) -> Result<()> {
+    let options = vec![
+        "Set Display Language",
+        "Translate an Ingredient",
+        "Back",
+    ];
+
+    let selection = Select::new()
+        .with_prompt("Settings")
+        .items(&options)
+        .interact()?;
+
+    match selection {
+        0 => {
+            let lang_options = vec!["Turkish (original)", "English", "No preference"];
+            let lang_selection = Select::new()
+                .with_prompt("Display ingredient names in")
+                .items(&lang_options)
+                .interact()?;
+
+            *display_lang = match lang_selection {
+                0 => Some(Lang::Tur),
+                1 => Some(Lang::Eng),
+                _ => None,
+            };
+
+            println!("\n{}", "Display language updated.".green());
+        }
+        1 => translate_ingredient(db)?,
+        2 => {}
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+fn translate_ingredient(db: &RecipeDatabase) -> Result<()> {
+    let recipes = db.get_recipes(None)?;
+
+    if recipes.is_empty() {
+        println!("\n{}", "No saved recipes found!".green());
+        return Ok(());
+    }
+
+    let recipe_titles: Vec<String> = recipes.iter().map(|r| r.title.clone()).collect();
+
+    let recipe_selection = Select::new()
+        .with_prompt("Select a recipe")
+        .items(&recipe_titles)
+        .interact()?;
+
+    let recipe_id = recipes[recipe_selection].id.unwrap();
+    let ingredients = db.get_recipe_ingredients_with_ids(recipe_id)?;
+
+    if ingredients.is_empty() {
+        println!("\n{}", "No ingredients found for this recipe!".green());
+        return Ok(());
+    }
+
+    let ingredient_names: Vec<String> = ingredients.iter().map(|(_, name)| name.clone()).collect();
+
+    let ingredient_selection = Select::new()
+        .with_prompt("Select an ingredient to translate")
+        .items(&ingredient_names)
+        .interact()?;
+
+    let (ingredient_id, _) = ingredients[ingredient_selection];
+
+    let translated_name = Input::<String>::new()
+        .with_prompt("Translated name")
+        .interact_text()?;
+
+    db.set_ingredient_translation(ingredient_id, &translated_name)?;
+
+    println!("\n{}", "Translation saved.".green());
+
+    Ok(())
+}
+
+fn clear_stale_cache(cache: &PageCache) -> Result<()> {
+    let cleared = cache.clear_stale()?;
+    println!("\n{}", format!("Removed {} stale cache entries.", cleared).green());
+    Ok(())
+}
+
+fn add_to_shopping_list(db: &RecipeDatabase, opts: RequestOpts) -> Result<()> {
     let add_options = vec![
         "Add from Saved Recipes",
         "Add Manually",
@@ -53,7 +149,7 @@ fn add_to_shopping_list(db: &RecipeDatabase) -> Result<()> {
         .interact()?;
 
     match selection {
-        0 => add_ingredients_from_recipes(db)?,
+        0 => add_ingredients_from_recipes(db, opts)?,
         1 => add_manual_ingredients(db)?,
         2 => return Ok(()), // Cancel
         _ => unreachable!(),
@@ -62,7 +158,7 @@ fn add_to_shopping_list(db: &RecipeDatabase) -> Result<()> {
     Ok(())
 }
 
-fn add_ingredients_from_recipes(db: &RecipeDatabase) -> Result<()> {
+fn add_ingredients_from_recipes(db: &RecipeDatabase, opts: RequestOpts) -> Result<()> {
     // Fetch saved recipes
     let recipes = db.get_recipes(None)?;
 
@@ -73,7 +169,7 @@ fn add_ingredients_from_recipes(db: &RecipeDatabase) -> Result<()> {
 
     // Let user select a recipe
     let recipe_titles: Vec<String> = recipes.iter().map(|r| r.title.clone()).collect();
-    
+
     let recipe_selection = Select::new()
         .with_prompt("Select a recipe to view ingredients")
         .items(&recipe_titles)
@@ -81,18 +177,25 @@ fn add_ingredients_from_recipes(db: &RecipeDatabase) -> Result<()> {
 
     let selected_recipe = &recipes[recipe_selection];
 
-    // Fetch ingredients for the selected recipe
-    let ingredients = db.get_recipe_ingredients(selected_recipe.id.unwrap())?;
+    // Fetch ingredients for the selected recipe, keeping their parsed
+    // quantity/unit around so adding them doesn't lose the amount.
+    let recipe_id = selected_recipe.id.unwrap();
+    let ingredient_rows = db.get_ingredient_rows(recipe_id)?;
 
-    if ingredients.is_empty() {
+    if ingredient_rows.is_empty() {
         println!("\n{}", "No ingredients found for this recipe!".green());
         return Ok(());
     }
 
+    let ingredient_lines: Vec<String> = ingredient_rows
+        .iter()
+        .map(|row| row.display_line(opts))
+        .collect();
+
     // Let user select ingredients to add to shopping list
     let selected_ingredient_indices = MultiSelect::new()
         .with_prompt("Select ingredients to add to shopping list")
-        .items(&ingredients)
+        .items(&ingredient_lines)
         .interact()?;
 
     if selected_ingredient_indices.is_empty() {
@@ -100,23 +203,21 @@ fn add_ingredients_from_recipes(db: &RecipeDatabase) -> Result<()> {
         return Ok(());
     }
 
-    // Collect selected ingredients
-    let selected_ingredients: Vec<String> = selected_ingredient_indices
+    // Collect selected ingredient rows
+    let selected_rows: Vec<_> = selected_ingredient_indices
         .iter()
-        .map(|&idx| ingredients[idx].clone())
+        .map(|&idx| ingredient_rows[idx].clone())
         .collect();
 
     // Confirm adding ingredients
     if Confirm::new()
-        .with_prompt(format!("Add {} ingredient(s) to shopping list?", selected_ingredients.len()))
+        .with_prompt(format!("Add {} ingredient(s) to shopping list?", selected_rows.len()))
         .interact()?
     {
-        // Add ingredients to database
-        for ingredient in &selected_ingredients {
-            db.add_ingredients(selected_recipe.id.unwrap(), &[ingredient.clone()])?;
-        }
+        // Add ingredients to database, preserving their parsed quantity/unit
+        db.add_ingredient_rows(recipe_id, &selected_rows)?;
 
-        println!("{} ingredient(s) added to shopping list!", selected_ingredients.len());
+        println!("{} ingredient(s) added to shopping list!", selected_rows.len());
     }
 
     Ok(())
@@ -156,14 +257,41 @@ fn add_manual_ingredients(db: &RecipeDatabase) -> Result<()> {
     Ok(())
 }
 
-fn search_and_save_recipe(db: &RecipeDatabase) -> Result<()> {
+fn search_and_save_recipe(db: &RecipeDatabase, cache: &PageCache) -> Result<()> {
     // Specify String as the type for Input
     let search_term = Input::<String>::new()
         .with_prompt("Enter recipe search term")
         .interact_text()?;
 
-    // Rest of the function remains the same
-    let search_results = search_recipes(&search_term)?;
+    let force_refresh = Confirm::new()
+        .with_prompt("Ignore cached pages and force a fresh fetch?")
+        .default(false)
+        .interact()?;
+
+    let sources = available_sources();
+    let mut source_options: Vec<String> = sources.iter().map(|s| s.name().to_string()).collect();
+    source_options.push("All sources".to_string());
+
+    let source_selection = Select::new()
+        .with_prompt("Which site should be searched?")
+        .items(&source_options)
+        .interact()?;
+
+    // Pair each result with the id of the source it came from, so the
+    // recipe picked below is re-fetched with that site's own selectors.
+    let mut search_results: Vec<(String, SearchResult)> = Vec::new();
+    if source_selection == sources.len() {
+        for source in &sources {
+            for result in source.search(&search_term, cache, force_refresh)? {
+                search_results.push((source.id().to_string(), result));
+            }
+        }
+    } else {
+        let source = &sources[source_selection];
+        for result in source.search(&search_term, cache, force_refresh)? {
+            search_results.push((source.id().to_string(), result));
+        }
+    }
 
     if search_results.is_empty() {
         println!("\n{}", "No recipes found!".green());
@@ -171,7 +299,7 @@ fn search_and_save_recipe(db: &RecipeDatabase) -> Result<()> {
     }
 
     // Convert search results to a vector of titles
-    let result_titles: Vec<String> = search_results.iter().map(|r| r.title.clone()).collect();
+    let result_titles: Vec<String> = search_results.iter().map(|(_, r)| r.title.clone()).collect();
 
     // Let user select a recipe to save
     let selection = Select::new()
@@ -179,9 +307,10 @@ fn search_and_save_recipe(db: &RecipeDatabase) -> Result<()> {
         .items(&result_titles)
         .interact()?;
 
-    // Fetch recipe details
-    let selected_recipe = &search_results[selection];
-    let recipe_details = fetch_recipe_details(&selected_recipe.link)?;
+    // Fetch recipe details using the source it was found on
+    let (source_id, selected_recipe) = &search_results[selection];
+    let source = source_by_id(source_id).expect("search result source must still be registered");
+    let recipe_details = source.fetch_details(&selected_recipe.link, cache, force_refresh)?;
 
     // Prompt for category with String type
     let category = Input::<String>::new()
@@ -195,7 +324,11 @@ fn search_and_save_recipe(db: &RecipeDatabase) -> Result<()> {
         title: selected_recipe.title.clone(),
         link: selected_recipe.link.clone(),
         category: if category.is_empty() { None } else { Some(category) },
-        steps: Some(recipe_details.steps.join("\n")),
+        steps: Some(recipe_details.instructions.join("\n")),
+        source: Some(source_id.clone()),
+        description: None,
+        estimate_time: None,
+        servings: None,
     };
 
     // Save recipe to database
@@ -209,7 +342,7 @@ fn search_and_save_recipe(db: &RecipeDatabase) -> Result<()> {
     Ok(())
 }
 
-fn view_saved_recipes(db: &RecipeDatabase) -> Result<()> {
+fn view_saved_recipes(db: &RecipeDatabase, opts: RequestOpts) -> Result<()> {
     // Allow filtering by category
     let filter_category = Confirm::new()
         .with_prompt("Do you want to filter recipes by category?")
@@ -243,18 +376,49 @@ fn view_saved_recipes(db: &RecipeDatabase) -> Result<()> {
         .interact()?;
 
     let selected_recipe = &recipes[selection];
-
-    // Fetch ingredients for the selected recipe
-    let ingredients = db.get_recipe_ingredients(selected_recipe.id.unwrap())?;
+    let recipe_id = selected_recipe.id.unwrap();
 
     // Display recipe details
     println!("\n{}", "Recipe Details:".blue().bold());
     println!("Title: {}", selected_recipe.title);
-    
+
     if let Some(category) = &selected_recipe.category {
         println!("Category: {}", category);
     }
 
+    if let Some(description) = &selected_recipe.description {
+        println!("Description: {}", description);
+    }
+
+    if let Some(estimate_time) = selected_recipe.estimate_time {
+        println!("Prep time: {} min", estimate_time);
+    }
+
+    if let Some(servings) = selected_recipe.servings {
+        println!("Servings: {}", servings);
+    }
+
+    // Offer to scale ingredient quantities when the recipe has a servings count
+    let ingredients = if let Some(servings) = selected_recipe.servings {
+        let rescale = Confirm::new()
+            .with_prompt("Scale ingredient quantities for a different serving size?")
+            .default(false)
+            .interact()?;
+
+        if rescale {
+            let target_servings = Input::<i64>::new()
+                .with_prompt("Target number of servings")
+                .default(servings)
+                .interact_text()?;
+
+            db.get_recipe_ingredients_scaled(recipe_id, target_servings, opts)?
+        } else {
+            db.get_recipe_ingredients(recipe_id, opts)?
+        }
+    } else {
+        db.get_recipe_ingredients(recipe_id, opts)?
+    };
+
     println!("\n{}:", "Ingredients".blue());
     for ingredient in ingredients {
         println!("- {}", ingredient);
@@ -270,17 +434,141 @@ fn view_saved_recipes(db: &RecipeDatabase) -> Result<()> {
     Ok(())
 }
 
-fn view_shopping_list(db: &RecipeDatabase) -> Result<()> {
-    let shopping_list = db.get_shopping_list()?;
-    
+fn edit_recipe(db: &RecipeDatabase) -> Result<()> {
+    let recipes = db.get_recipes(None)?;
+
+    if recipes.is_empty() {
+        println!("\n{}", "No saved recipes found!".green());
+        return Ok(());
+    }
+
+    let recipe_titles: Vec<String> = recipes.iter().map(|r| r.title.clone()).collect();
+
+    let selection = Select::new()
+        .with_prompt("Select a recipe to edit")
+        .items(&recipe_titles)
+        .interact()?;
+
+    let recipe = recipes[selection].clone();
+    let recipe_id = recipe.id.unwrap();
+
+    let title = Input::<String>::new()
+        .with_prompt("Title")
+        .with_initial_text(recipe.title.clone())
+        .interact_text()?;
+
+    let category = Input::<String>::new()
+        .with_prompt("Category")
+        .with_initial_text(recipe.category.clone().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+
+    let description = Input::<String>::new()
+        .with_prompt("Description")
+        .with_initial_text(recipe.description.clone().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+
+    let estimate_time_input = Input::<String>::new()
+        .with_prompt("Prep time in minutes")
+        .with_initial_text(recipe.estimate_time.map(|t| t.to_string()).unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+    let estimate_time = if estimate_time_input.is_empty() {
+        None
+    } else {
+        Some(estimate_time_input.parse()?)
+    };
+
+    let servings_input = Input::<String>::new()
+        .with_prompt("Servings")
+        .with_initial_text(recipe.servings.map(|s| s.to_string()).unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+    let servings = if servings_input.is_empty() {
+        None
+    } else {
+        Some(servings_input.parse()?)
+    };
+
+    // Reorder/edit steps in the user's $EDITOR, one step per line
+    let steps_input = Editor::new()
+        .edit(recipe.steps.as_deref().unwrap_or(""))?
+        .unwrap_or_else(|| recipe.steps.clone().unwrap_or_default());
+    let steps = if steps_input.trim().is_empty() { None } else { Some(steps_input) };
+
+    // Let the user drop ingredients, then optionally add new ones. Always
+    // edit the original stored names, not a translated display name. Kept
+    // rows are left in place rather than re-parsed from a display string,
+    // so their quantity/unit/translation survive the edit.
+    let existing_rows = db.get_ingredient_rows(recipe_id)?;
+    let existing_lines: Vec<String> = existing_rows
+        .iter()
+        .map(|row| row.display_line(RequestOpts::default()))
+        .collect();
+
+    let kept_indices = if existing_rows.is_empty() {
+        Vec::new()
+    } else {
+        MultiSelect::new()
+            .with_prompt("Select ingredients to keep (unchecked ones are removed)")
+            .items(&existing_lines)
+            .defaults(&vec![true; existing_lines.len()])
+            .interact()?
+    };
+
+    let removed_ids: Vec<i64> = existing_rows
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !kept_indices.contains(idx))
+        .map(|(_, row)| row.id)
+        .collect();
+
+    let new_ingredients_input = Input::<String>::new()
+        .with_prompt("Add ingredients (comma-separated, optional)")
+        .allow_empty(true)
+        .interact_text()?;
+
+    let new_ingredients: Vec<String> = new_ingredients_input
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let updated_recipe = Recipe {
+        id: Some(recipe_id),
+        title,
+        link: recipe.link.clone(),
+        category: if category.is_empty() { None } else { Some(category) },
+        steps,
+        source: recipe.source.clone(),
+        description: if description.is_empty() { None } else { Some(description) },
+        estimate_time,
+        servings,
+    };
+
+    db.update_recipe(&updated_recipe)?;
+    db.delete_ingredients(&removed_ids)?;
+    if !new_ingredients.is_empty() {
+        db.add_ingredients(recipe_id, &new_ingredients)?;
+    }
+
+    println!("\n{} {} {}", "Recipe".green(), updated_recipe.title.bold(), "updated successfully!".green());
+
+    Ok(())
+}
+
+fn view_shopping_list(db: &RecipeDatabase, opts: RequestOpts) -> Result<()> {
+    let shopping_list = db.get_shopping_list(opts)?;
+
     if shopping_list.is_empty() {
         println!("\n{}", "Shopping list is empty!".green());
         return Ok(());
     }
 
     println!("\n{}", "Shopping List:".blue().bold());
-    for (i, ingredient) in shopping_list.iter().enumerate() {
-        println!("{}. {}", i + 1, ingredient);
+    for (i, item) in shopping_list.iter().enumerate() {
+        println!("{}. {}", i + 1, item);
     }
 
     Ok(())
@@ -349,8 +637,8 @@ fn delete_saved_recipes(db: &RecipeDatabase) -> Result<()> {
 }
 
 
-fn mark_ingredients(db: &mut RecipeDatabase) -> Result<()> {
-    let shopping_list = db.get_shopping_list()?;
+fn mark_ingredients(db: &mut RecipeDatabase, opts: RequestOpts) -> Result<()> {
+    let shopping_list = db.get_shopping_list(opts)?;
     
     if shopping_list.is_empty() {
         println!("\n{}", "No ingredients to mark!".green());
@@ -367,7 +655,7 @@ fn mark_ingredients(db: &mut RecipeDatabase) -> Result<()> {
         // Collect the selected ingredients
         let marked_ingredients: Vec<String> = selected_ingredients
             .iter()
-            .map(|&idx| shopping_list[idx].clone())
+            .map(|&idx| shopping_list[idx].name.clone())
             .collect();
 
         // Confirm marking ingredients as bought
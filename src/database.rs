@@ -1,12 +1,21 @@
 use rusqlite::{Connection, Result, params};
 
-#[derive(Debug)]
+use crate::ingredient::parse_ingredient_line;
+
+#[derive(Debug, Clone)]
 pub struct Recipe {
     pub id: Option<i64>,
     pub title: String,
     pub link: String,
     pub category: Option<String>,
     pub steps: Option<String>,
+    /// `RecipeSource::id()` of the site this recipe was scraped from, so it
+    /// can be re-fetched with the right selectors later.
+    pub source: Option<String>,
+    pub description: Option<String>,
+    /// Estimated preparation time in minutes.
+    pub estimate_time: Option<i64>,
+    pub servings: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -15,6 +24,134 @@ pub struct RecipeDatabase {
     conn: Connection,
 }
 
+/// Language an ingredient name is stored or requested in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Tur,
+    Eng,
+}
+
+impl Lang {
+    fn as_db_str(self) -> &'static str {
+        match self {
+            Lang::Tur => "tur",
+            Lang::Eng => "eng",
+        }
+    }
+
+    fn from_db_str(s: &str) -> Lang {
+        match s {
+            "eng" => Lang::Eng,
+            _ => Lang::Tur,
+        }
+    }
+}
+
+/// Options that shape how ingredient names are returned. `lang: None` keeps
+/// today's behavior of always showing the stored name; `lang: Some(_)`
+/// requests a specific display language, falling back to the original name
+/// when no translation has been recorded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestOpts {
+    pub lang: Option<Lang>,
+}
+
+/// Picks which name to display for an ingredient stored in `lang` with an
+/// optional `translated_name`, given the caller's requested language.
+fn resolve_name(name: &str, lang: Lang, translated_name: Option<&str>, requested: Option<Lang>) -> String {
+    match requested {
+        Some(requested_lang) if requested_lang != lang => {
+            translated_name.map(str::to_string).unwrap_or_else(|| name.to_string())
+        }
+        _ => name.to_string(),
+    }
+}
+
+/// Sums two optional quantities, treating a missing one as "no extra amount"
+/// rather than zero, so e.g. two unit-less, countless entries stay `None`
+/// instead of becoming a bogus `0 ×`.
+fn merge_quantity(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(x), Some(y)) => Some(x + y),
+        (Some(x), None) | (None, Some(x)) => Some(x),
+        (None, None) => None,
+    }
+}
+
+/// One line of an aggregated shopping list. Entries that share a normalized
+/// name and unit have their quantities summed into a single item; this
+/// includes unit-less entries (e.g. "3 eggs" + "3 eggs" -> "6 × eggs"), but
+/// mismatched-unit entries for the same name stay as separate items.
+#[derive(Debug, Clone)]
+pub struct ShoppingItem {
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+}
+
+impl std::fmt::Display for ShoppingItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.quantity, &self.unit) {
+            (Some(quantity), Some(unit)) => {
+                write!(f, "{} {} {}", format_quantity(quantity), unit, self.name)
+            }
+            (Some(quantity), None) => write!(f, "{} × {}", format_quantity(quantity), self.name),
+            (None, _) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+fn format_quantity(quantity: f64) -> String {
+    if quantity.fract() == 0.0 {
+        format!("{}", quantity as i64)
+    } else {
+        format!("{:.2}", quantity)
+    }
+}
+
+/// Normalizes an ingredient name for grouping: trims, lowercases, and
+/// collapses repeated whitespace.
+fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase().split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Renders a single ingredient line as "quantity unit name", like the
+/// shopping list, except that a unit-less quantity is written as
+/// "quantity name" without the shopping list's "×".
+fn format_ingredient_line(quantity: Option<f64>, unit: &Option<String>, name: &str) -> String {
+    match (quantity, unit) {
+        (Some(quantity), Some(unit)) => format!("{} {} {}", format_quantity(quantity), unit, name),
+        (Some(quantity), None) => format!("{} {}", format_quantity(quantity), name),
+        (None, _) => name.to_string(),
+    }
+}
+
+/// A recipe's ingredient row with its parsed quantity/unit intact, so
+/// callers can display, copy, or selectively drop ingredients without
+/// round-tripping them through a formatted string (which would lose the
+/// parsed amount).
+#[derive(Debug, Clone)]
+pub struct IngredientRow {
+    pub id: i64,
+    pub name: String,
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub lang: Lang,
+    pub translated_name: Option<String>,
+}
+
+impl IngredientRow {
+    /// The name to show for this row, honoring `opts.lang`.
+    pub fn display_name(&self, opts: RequestOpts) -> String {
+        resolve_name(&self.name, self.lang, self.translated_name.as_deref(), opts.lang)
+    }
+
+    /// "quantity unit name" formatted for display, honoring `opts.lang`.
+    pub fn display_line(&self, opts: RequestOpts) -> String {
+        format_ingredient_line(self.quantity, &self.unit, &self.display_name(opts))
+    }
+}
+
 impl RecipeDatabase {
     pub fn new() -> Result<Self> {
         let conn = Connection::open("recipes.db")?;
@@ -26,9 +163,27 @@ impl RecipeDatabase {
                 title TEXT NOT NULL,
                 link TEXT NOT NULL,
                 category TEXT,
-                steps TEXT
+                steps TEXT,
+                source TEXT,
+                description TEXT,
+                estimate_time INTEGER,
+                servings INTEGER
             )", [])?;
-        
+
+        // Migrate recipes tables created before these columns existed.
+        for migration in [
+            "ALTER TABLE recipes ADD COLUMN source TEXT",
+            "ALTER TABLE recipes ADD COLUMN description TEXT",
+            "ALTER TABLE recipes ADD COLUMN estimate_time INTEGER",
+            "ALTER TABLE recipes ADD COLUMN servings INTEGER",
+        ] {
+            if let Err(err) = conn.execute(migration, []) {
+                if !err.to_string().contains("duplicate column name") {
+                    return Err(err);
+                }
+            }
+        }
+
         // Create ingredients table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS ingredients (
@@ -36,26 +191,71 @@ impl RecipeDatabase {
                 name TEXT NOT NULL,
                 recipe_id INTEGER,
                 have INTEGER DEFAULT 0,
+                quantity REAL,
+                unit TEXT,
+                lang TEXT NOT NULL DEFAULT 'tur',
+                translated_name TEXT,
                 FOREIGN KEY(recipe_id) REFERENCES recipes(id) ON DELETE CASCADE
             )", [])?;
-        
+
+        // Migrate ingredients tables created before quantity/unit/lang existed.
+        for migration in [
+            "ALTER TABLE ingredients ADD COLUMN quantity REAL",
+            "ALTER TABLE ingredients ADD COLUMN unit TEXT",
+            "ALTER TABLE ingredients ADD COLUMN lang TEXT NOT NULL DEFAULT 'tur'",
+            "ALTER TABLE ingredients ADD COLUMN translated_name TEXT",
+        ] {
+            if let Err(err) = conn.execute(migration, []) {
+                if !err.to_string().contains("duplicate column name") {
+                    return Err(err);
+                }
+            }
+        }
+
         Ok(RecipeDatabase { conn })
     }
     
     pub fn add_recipe(&self, recipe: &Recipe) -> Result<i64> {
         self.conn.execute(
-            "INSERT INTO recipes (title, link, category, steps) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO recipes (title, link, category, steps, source, description, estimate_time, servings)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
-                recipe.title, 
-                recipe.link, 
-                recipe.category, 
-                recipe.steps
+                recipe.title,
+                recipe.link,
+                recipe.category,
+                recipe.steps,
+                recipe.source,
+                recipe.description,
+                recipe.estimate_time,
+                recipe.servings
             ]
         )?;
-        
+
         Ok(self.conn.last_insert_rowid())
     }
 
+    pub fn update_recipe(&self, recipe: &Recipe) -> Result<()> {
+        let recipe_id = recipe.id.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+
+        self.conn.execute(
+            "UPDATE recipes SET title = ?1, link = ?2, category = ?3, steps = ?4, source = ?5,
+                description = ?6, estimate_time = ?7, servings = ?8 WHERE id = ?9",
+            params![
+                recipe.title,
+                recipe.link,
+                recipe.category,
+                recipe.steps,
+                recipe.source,
+                recipe.description,
+                recipe.estimate_time,
+                recipe.servings,
+                recipe_id
+            ]
+        )?;
+
+        Ok(())
+    }
+
 
     pub fn delete_recipe(&self, recipe_id: i64) -> Result<()> {
     let deleted_count = self.conn.execute(
@@ -71,8 +271,10 @@ impl RecipeDatabase {
 }
     pub fn get_recipes(&self, category: Option<&str>) -> Result<Vec<Recipe>> {
         let query = match category {
-            Some(_) => "SELECT id, title, link, category, steps FROM recipes WHERE category = ?1",
-            None => "SELECT id, title, link, category, steps FROM recipes"
+            Some(_) => "SELECT id, title, link, category, steps, source, description, estimate_time, servings
+                        FROM recipes WHERE category = ?1",
+            None => "SELECT id, title, link, category, steps, source, description, estimate_time, servings
+                     FROM recipes"
         };
 
         let mut stmt = self.conn.prepare(query)?;
@@ -84,6 +286,10 @@ impl RecipeDatabase {
                 link: row.get(2)?,
                 category: row.get(3)?,
                 steps: row.get(4)?,
+                source: row.get(5)?,
+                description: row.get(6)?,
+                estimate_time: row.get(7)?,
+                servings: row.get(8)?,
             })
         };
 
@@ -100,80 +306,258 @@ impl RecipeDatabase {
         Ok(recipes)
     }
 
-    pub fn get_recipe_ingredients(&self, recipe_id: i64) -> Result<Vec<String>> {
+    /// Returns a recipe's ingredients as "quantity unit name" display lines,
+    /// honoring `opts.lang`. Use `get_ingredient_rows` instead when the
+    /// parsed quantity/unit/id are needed rather than a formatted string.
+    pub fn get_recipe_ingredients(&self, recipe_id: i64, opts: RequestOpts) -> Result<Vec<String>> {
+        Ok(self.get_ingredient_rows(recipe_id)?
+            .iter()
+            .map(|row| row.display_line(opts))
+            .collect())
+    }
+
+    /// Returns a recipe's ingredients with their row id and parsed
+    /// quantity/unit intact, e.g. to selectively drop rows or copy them to
+    /// another recipe without losing the parsed amount.
+    pub fn get_ingredient_rows(&self, recipe_id: i64) -> Result<Vec<IngredientRow>> {
         let mut stmt = self.conn.prepare(
-            "SELECT name FROM ingredients WHERE recipe_id = ?1"
+            "SELECT id, name, quantity, unit, lang, translated_name FROM ingredients WHERE recipe_id = ?1"
         )?;
-        
+
+        let ingredient_iter = stmt.query_map(params![recipe_id], |row| {
+            Ok(IngredientRow {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                quantity: row.get(2)?,
+                unit: row.get(3)?,
+                lang: Lang::from_db_str(&row.get::<_, String>(4)?),
+                translated_name: row.get(5)?,
+            })
+        })?;
+
+        let mut ingredients = Vec::new();
+        for ingredient in ingredient_iter {
+            ingredients.push(ingredient?);
+        }
+
+        Ok(ingredients)
+    }
+
+    /// Returns a recipe's ingredients paired with their row id, so a
+    /// translation can be attached to a specific one afterwards.
+    pub fn get_recipe_ingredients_with_ids(&self, recipe_id: i64) -> Result<Vec<(i64, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, name FROM ingredients WHERE recipe_id = ?1"
+        )?;
+
         let ingredient_iter = stmt.query_map([recipe_id], |row| {
-            row.get(0)
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
         })?;
-        
+
         let mut ingredients = Vec::new();
         for ingredient in ingredient_iter {
             ingredients.push(ingredient?);
         }
-        
+
         Ok(ingredients)
     }
-    
-       pub fn add_ingredients(&self, recipe_id: i64, ingredients: &[String]) -> Result<()> {
+
+    /// Attaches a translated name to an existing ingredient.
+    pub fn set_ingredient_translation(&self, ingredient_id: i64, translated_name: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE ingredients SET translated_name = ?1 WHERE id = ?2",
+            params![translated_name, ingredient_id]
+        )?;
+
+        Ok(())
+    }
+
+    /// Like `get_recipe_ingredients`, but scales each parsed quantity
+    /// proportionally to `target_servings` based on the recipe's stored
+    /// `servings`. Ingredients without a parsed quantity, or recipes without
+    /// a servings count, are returned unscaled.
+    pub fn get_recipe_ingredients_scaled(&self, recipe_id: i64, target_servings: i64, opts: RequestOpts) -> Result<Vec<String>> {
+        let original_servings: Option<i64> = self.conn.query_row(
+            "SELECT servings FROM recipes WHERE id = ?1",
+            params![recipe_id],
+            |row| row.get(0),
+        )?;
+
+        let factor = match original_servings {
+            Some(servings) if servings > 0 => target_servings as f64 / servings as f64,
+            _ => 1.0,
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT name, quantity, unit, lang, translated_name FROM ingredients WHERE recipe_id = ?1"
+        )?;
+
+        let ingredient_iter = stmt.query_map(params![recipe_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?;
+
+        let mut lines = Vec::new();
+        for ingredient in ingredient_iter {
+            let (name, quantity, unit, lang, translated_name) = ingredient?;
+            let display_name = resolve_name(&name, Lang::from_db_str(&lang), translated_name.as_deref(), opts.lang);
+            let scaled_quantity = quantity.map(|q| q * factor);
+            lines.push(format_ingredient_line(scaled_quantity, &unit, &display_name));
+        }
+
+        Ok(lines)
+    }
+
+    /// Inserts one ingredient row with an already-known quantity/unit/lang,
+    /// shared by `add_ingredients` (which parses a raw line first) and
+    /// `add_ingredient_rows` (which copies an already-parsed row).
+    fn insert_ingredient_row(&self, recipe_id: i64, name: &str, quantity: Option<f64>, unit: Option<&str>, lang: &str) -> Result<()> {
+        // If recipe_id is 0, it means a manual entry
+        let query = if recipe_id == 0 {
+            "INSERT INTO ingredients (name, recipe_id, quantity, unit, lang) VALUES (?1, NULL, ?2, ?3, ?4)"
+        } else {
+            "INSERT INTO ingredients (name, recipe_id, quantity, unit, lang) VALUES (?1, ?2, ?3, ?4, ?5)"
+        };
+
+        if recipe_id == 0 {
+            self.conn.execute(query, params![name, quantity, unit, lang])?;
+        } else {
+            self.conn.execute(query, params![name, recipe_id, quantity, unit, lang])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn add_ingredients(&self, recipe_id: i64, ingredients: &[String]) -> Result<()> {
+        // Scraped and manually-entered ingredients are always in the site's
+        // original language; translations are attached afterwards.
+        let lang = Lang::Tur.as_db_str();
+
         for ingredient in ingredients {
-            // If recipe_id is 0, it means a manual entry
-            let query = if recipe_id == 0 {
-                "INSERT INTO ingredients (name, recipe_id) VALUES (?1, NULL)"
-            } else {
-                "INSERT INTO ingredients (name, recipe_id) VALUES (?1, ?2)"
-            };
+            let parsed = parse_ingredient_line(ingredient);
+            self.insert_ingredient_row(recipe_id, &parsed.name, parsed.quantity, parsed.unit.as_deref(), lang)?;
+        }
+        Ok(())
+    }
 
-            let params = if recipe_id == 0 {
-                params![ingredient]
-            } else {
-                params![ingredient, recipe_id]
-            };
+    /// Copies already-parsed ingredient rows (e.g. from another recipe) onto
+    /// `recipe_id`, preserving their quantity/unit/lang instead of
+    /// re-parsing a formatted display string.
+    pub fn add_ingredient_rows(&self, recipe_id: i64, rows: &[IngredientRow]) -> Result<()> {
+        for row in rows {
+            self.insert_ingredient_row(recipe_id, &row.name, row.quantity, row.unit.as_deref(), row.lang.as_db_str())?;
+        }
+        Ok(())
+    }
 
-            self.conn.execute(query, params)?;
+    /// Deletes specific ingredient rows by id, leaving every other row
+    /// untouched. Used to drop only the ingredients a user unchecked while
+    /// editing a recipe, rather than re-parsing every kept row.
+    pub fn delete_ingredients(&self, ids: &[i64]) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
         }
+
+        let placeholders: Vec<String> = ids.iter().map(|_| "?".to_string()).collect();
+        let query = format!("DELETE FROM ingredients WHERE id IN ({})", placeholders.join(", "));
+        self.conn.execute(&query, rusqlite::params_from_iter(ids))?;
+
         Ok(())
     }
-    
-    pub fn get_shopping_list(&self) -> Result<Vec<String>> {
+
+
+    pub fn get_shopping_list(&self, opts: RequestOpts) -> Result<Vec<ShoppingItem>> {
         let mut stmt = self.conn.prepare(
-            "SELECT name FROM ingredients WHERE have = 0"
+            "SELECT name, quantity, unit, lang, translated_name FROM ingredients WHERE have = 0"
         )?;
-        
+
         let ingredient_iter = stmt.query_map([], |row| {
-            row.get(0)
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<f64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
         })?;
-        
-        let mut shopping_list = Vec::new();
-        for ingredient in ingredient_iter {
-            shopping_list.push(ingredient?);
+
+        // Group rows that share a normalized name and unit (unit-less counts
+        // into unit-less counts), summing their quantities. Only a mismatch
+        // in unit for the same name keeps rows as separate items.
+        let mut shopping_list: Vec<ShoppingItem> = Vec::new();
+        for row in ingredient_iter {
+            let (name, quantity, unit, lang, translated_name) = row?;
+            let name = resolve_name(&name, Lang::from_db_str(&lang), translated_name.as_deref(), opts.lang);
+
+            let existing = shopping_list.iter_mut().find(|item| {
+                item.unit == unit && normalize_name(&item.name) == normalize_name(&name)
+            });
+
+            if let Some(item) = existing {
+                item.quantity = merge_quantity(item.quantity, quantity);
+            } else {
+                shopping_list.push(ShoppingItem { name, quantity, unit });
+            }
         }
-        
+
         Ok(shopping_list)
     }
-    
+
 
 
         pub fn mark_and_remove_ingredients(&mut self, ingredient_names: &[String]) -> Result<()> {
+        let targets: std::collections::HashSet<String> =
+            ingredient_names.iter().map(|name| normalize_name(name)).collect();
+
+        let matching_ids: Vec<i64> = {
+            let mut stmt = self.conn.prepare("SELECT id, name, translated_name FROM ingredients WHERE have = 0")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            })?;
+
+            let mut ids = Vec::new();
+            for row in rows {
+                let (id, name, translated_name) = row?;
+                let matches = targets.contains(&normalize_name(&name))
+                    || translated_name.as_deref().is_some_and(|t| targets.contains(&normalize_name(t)));
+                if matches {
+                    ids.push(id);
+                }
+            }
+            ids
+        };
+
+        if matching_ids.is_empty() {
+            return Ok(());
+        }
+
         // Start a transaction to ensure atomic operation
         let tx = self.conn.transaction()?;
 
+        let placeholders: Vec<String> = matching_ids.iter().map(|_| "?".to_string()).collect();
+
         // Update ingredients to mark as bought
-        let placeholders: Vec<String> = ingredient_names.iter().map(|_| "?".to_string()).collect();
         let update_query = format!(
-            "UPDATE ingredients SET have = 1 WHERE name IN ({})",
+            "UPDATE ingredients SET have = 1 WHERE id IN ({})",
             placeholders.join(", ")
         );
-        tx.execute(&update_query, rusqlite::params_from_iter(ingredient_names))?;
+        tx.execute(&update_query, rusqlite::params_from_iter(&matching_ids))?;
 
         // Delete ingredients from the ingredients table
         let delete_query = format!(
-            "DELETE FROM ingredients WHERE name IN ({})",
+            "DELETE FROM ingredients WHERE id IN ({})",
             placeholders.join(", ")
         );
-        tx.execute(&delete_query, rusqlite::params_from_iter(ingredient_names))?;
+        tx.execute(&delete_query, rusqlite::params_from_iter(&matching_ids))?;
 
         // Commit the transaction
         tx.commit()?;
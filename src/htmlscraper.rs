@@ -5,6 +5,8 @@ use urlencoding;
 use scraper::html::Html;
 use scraper::selector::Selector;
 
+use crate::cache::PageCache;
+
 #[derive(Debug, Clone)]
 pub struct SearchResult {
     pub title: String,
@@ -26,51 +28,120 @@ fn create_client() -> Result<reqwest::blocking::Client> {
     let client = reqwest::blocking::Client::builder()
         .default_headers(headers)
         .build()?;
-    
+
     Ok(client)
 }
 
-pub fn search_recipes(search_term: &str) -> Result<Vec<SearchResult>> {
-    let url = format!("https://www.nefisyemektarifleri.com/ara/page/1/?s={}", urlencoding::encode(search_term));
-    
+/// Fetches `url` through `cache`, skipping the network on a fresh hit.
+/// Passing `force_refresh` always re-fetches and overwrites the cached entry.
+fn cached_get(cache: &PageCache, url: &str, force_refresh: bool) -> Result<String> {
+    if !force_refresh {
+        if let Some(body) = cache.get(url)? {
+            return Ok(body);
+        }
+    }
+
     let client = create_client()?;
-    let response = client.get(&url).send()?;
-    let body = response.text()?;
-    
-    let document = Html::parse_document(&body);
-    
-    let title_selector = Selector::parse("a.title").unwrap();
-    
-    let results: Vec<SearchResult> = document.select(&title_selector)
-        .map(|element| SearchResult {
-            title: element.text().collect::<String>(),
-            link: element.value().attr("href").unwrap_or("").to_string(),
+    let body = client.get(url).send()?.text()?;
+    cache.put(url, &body)?;
+
+    Ok(body)
+}
+
+/// A scrapeable recipe website. Each implementor owns its base URL and CSS
+/// selectors, so adding a new site is a new struct rather than a change to
+/// an existing source's `search`/`fetch_details`.
+pub trait RecipeSource {
+    /// Stable identifier persisted on saved `Recipe`s so they can be
+    /// re-fetched from the right site later.
+    fn id(&self) -> &'static str;
+
+    /// Human-readable name shown when the user picks a source.
+    fn name(&self) -> &'static str;
+
+    fn search(&self, term: &str, cache: &PageCache, force_refresh: bool) -> Result<Vec<SearchResult>>;
+
+    fn fetch_details(&self, url: &str, cache: &PageCache, force_refresh: bool) -> Result<RecipeDetails>;
+}
+
+/// Scraper for nefisyemektarifleri.com.
+pub struct NefisYemekTarifleriSource {
+    base_url: String,
+    title_selector: String,
+    materials_selector: String,
+    instructions_selector: String,
+}
+
+impl NefisYemekTarifleriSource {
+    pub fn new() -> Self {
+        NefisYemekTarifleriSource {
+            base_url: "https://www.nefisyemektarifleri.com".to_string(),
+            title_selector: "a.title".to_string(),
+            materials_selector: "ul.recipe-materials li".to_string(),
+            instructions_selector: "ol.recipe-instructions > li".to_string(),
+        }
+    }
+}
+
+impl RecipeSource for NefisYemekTarifleriSource {
+    fn id(&self) -> &'static str {
+        "nefisyemektarifleri"
+    }
+
+    fn name(&self) -> &'static str {
+        "Nefis Yemek Tarifleri"
+    }
+
+    fn search(&self, term: &str, cache: &PageCache, force_refresh: bool) -> Result<Vec<SearchResult>> {
+        let url = format!("{}/ara/page/1/?s={}", self.base_url, urlencoding::encode(term));
+
+        let body = cached_get(cache, &url, force_refresh)?;
+
+        let document = Html::parse_document(&body);
+
+        let title_selector = Selector::parse(&self.title_selector).unwrap();
+
+        let results: Vec<SearchResult> = document.select(&title_selector)
+            .map(|element| SearchResult {
+                title: element.text().collect::<String>(),
+                link: element.value().attr("href").unwrap_or("").to_string(),
+            })
+            .collect();
+
+        Ok(results)
+    }
+
+    fn fetch_details(&self, url: &str, cache: &PageCache, force_refresh: bool) -> Result<RecipeDetails> {
+        let body = cached_get(cache, url, force_refresh)?;
+
+        let document = Html::parse_document(&body);
+
+        let materials_selector = Selector::parse(&self.materials_selector).unwrap();
+        let instructions_selector = Selector::parse(&self.instructions_selector).unwrap();
+
+        let materials: Vec<String> = document.select(&materials_selector)
+            .map(|element| element.text().collect::<String>())
+            .collect();
+
+        let instructions: Vec<String> = document.select(&instructions_selector)
+            .map(|element| element.text().collect::<String>())
+            .collect();
+
+        Ok(RecipeDetails {
+            materials,
+            instructions
         })
-        .collect();
-    
-    Ok(results)
+    }
 }
 
-pub fn fetch_recipe_details(url: &str) -> Result<RecipeDetails> {
-    let client = create_client()?;
-    let response = client.get(url).send()?;
-    let body = response.text()?;
-    
-    let document = Html::parse_document(&body);
-    
-    let materials_selector = Selector::parse("ul.recipe-materials li").unwrap();
-    let instructions_selector = Selector::parse("ol.recipe-instructions > li").unwrap();
-    
-    let materials: Vec<String> = document.select(&materials_selector)
-        .map(|element| element.text().collect::<String>())
-        .collect();
-    
-    let instructions: Vec<String> = document.select(&instructions_selector)
-        .map(|element| element.text().collect::<String>())
-        .collect();
-    
-    Ok(RecipeDetails { 
-        materials, 
-        instructions 
-    })
+/// All recipe sources the CLI can search. Add a new site here once it has a
+/// `RecipeSource` implementor.
+pub fn available_sources() -> Vec<Box<dyn RecipeSource>> {
+    vec![Box::new(NefisYemekTarifleriSource::new())]
+}
+
+/// Looks up a previously available source by its persisted `id()`, e.g. to
+/// re-fetch a recipe that was saved with that source.
+pub fn source_by_id(id: &str) -> Option<Box<dyn RecipeSource>> {
+    available_sources().into_iter().find(|source| source.id() == id)
 }
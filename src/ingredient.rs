@@ -0,0 +1,180 @@
+// Known unit tokens recognized after a quantity, matched case-insensitively.
+const UNITS: &[&str] = &[
+    "g", "kg", "mg", "ml", "l", "cl", "tbsp", "tsp", "cup", "cups", "oz", "lb",
+    "adet", "paket", "dilim", "demet", "kaşık", "bardak",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedIngredient {
+    pub quantity: Option<f64>,
+    pub unit: Option<String>,
+    pub name: String,
+}
+
+/// Value of a glued Unicode fraction character, if `c` is one we recognize.
+fn unicode_fraction_value(c: char) -> Option<f64> {
+    match c {
+        '½' => Some(0.5),
+        '¾' => Some(0.75),
+        '¼' => Some(0.25),
+        _ => None,
+    }
+}
+
+/// Parses a leading quantity off `s`, returning the value and the byte index
+/// where the quantity ends. Handles plain integers/decimals, ASCII fractions
+/// like `1/2`, bare Unicode fractions, and a whole number glued to a Unicode
+/// fraction like `1½` (summed to `1.5`).
+fn parse_leading_quantity(s: &str) -> Option<(f64, usize)> {
+    let bytes_consumed_digits = |s: &str| {
+        s.char_indices()
+            .take_while(|(_, c)| c.is_ascii_digit())
+            .last()
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0)
+    };
+
+    if let Some((_, first)) = s.char_indices().next() {
+        if let Some(value) = unicode_fraction_value(first) {
+            let end = first.len_utf8();
+            return Some((value, end));
+        }
+    }
+
+    let int_len = bytes_consumed_digits(s);
+    if int_len == 0 {
+        return None;
+    }
+
+    // Whole number glued to a Unicode fraction, e.g. "1½".
+    if let Some(c) = s[int_len..].chars().next() {
+        if let Some(frac) = unicode_fraction_value(c) {
+            let whole: f64 = s[..int_len].parse().ok()?;
+            return Some((whole + frac, int_len + c.len_utf8()));
+        }
+    }
+
+    // ASCII fraction, e.g. "1/2".
+    if s[int_len..].starts_with('/') {
+        let rest = &s[int_len + 1..];
+        let den_len = bytes_consumed_digits(rest);
+        if den_len > 0 {
+            let numerator: f64 = s[..int_len].parse().ok()?;
+            let denominator: f64 = rest[..den_len].parse().ok()?;
+            if denominator != 0.0 {
+                return Some((numerator / denominator, int_len + 1 + den_len));
+            }
+        }
+    }
+
+    // Decimal point, e.g. "1.5".
+    if s[int_len..].starts_with('.') {
+        let rest = &s[int_len + 1..];
+        let frac_len = bytes_consumed_digits(rest);
+        if frac_len > 0 {
+            let end = int_len + 1 + frac_len;
+            let value: f64 = s[..end].parse().ok()?;
+            return Some((value, end));
+        }
+    }
+
+    let value: f64 = s[..int_len].parse().ok()?;
+    Some((value, int_len))
+}
+
+/// Splits a scraped ingredient line into quantity, unit, and name, e.g.
+/// `"135g plain flour"` -> `(Some(135.0), Some("g"), "plain flour")`.
+/// Lines without a recognizable leading quantity are returned unchanged as
+/// `name` with `quantity` and `unit` set to `None`. A unit with nothing
+/// after it (e.g. `"250ml"`) is kept as the name instead of being split off,
+/// so the result never has an empty name.
+pub fn parse_ingredient_line(line: &str) -> ParsedIngredient {
+    let trimmed = line.trim();
+
+    let Some((quantity, qty_end)) = parse_leading_quantity(trimmed) else {
+        return ParsedIngredient {
+            quantity: None,
+            unit: None,
+            name: trimmed.to_string(),
+        };
+    };
+
+    let rest = trimmed[qty_end..].trim_start();
+
+    let unit_token_len = rest
+        .char_indices()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(i, _)| i)
+        .unwrap_or(rest.len());
+    let candidate = &rest[..unit_token_len];
+
+    let name_after_unit = rest[unit_token_len..].trim_start();
+
+    if !candidate.is_empty() && !name_after_unit.is_empty() && UNITS.iter().any(|u| u.eq_ignore_ascii_case(candidate)) {
+        ParsedIngredient {
+            quantity: Some(quantity),
+            unit: Some(candidate.to_lowercase()),
+            name: name_after_unit.to_string(),
+        }
+    } else {
+        ParsedIngredient {
+            quantity: Some(quantity),
+            unit: None,
+            name: rest.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(quantity: Option<f64>, unit: Option<&str>, name: &str) -> ParsedIngredient {
+        ParsedIngredient {
+            quantity,
+            unit: unit.map(str::to_string),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn plain_quantity_and_unit() {
+        assert_eq!(parse_ingredient_line("135g plain flour"), parsed(Some(135.0), Some("g"), "plain flour"));
+        assert_eq!(parse_ingredient_line("2 cups sugar"), parsed(Some(2.0), Some("cups"), "sugar"));
+    }
+
+    #[test]
+    fn ascii_fraction() {
+        assert_eq!(parse_ingredient_line("1/2 tsp salt"), parsed(Some(0.5), Some("tsp"), "salt"));
+    }
+
+    #[test]
+    fn bare_unicode_fraction() {
+        assert_eq!(parse_ingredient_line("½ l milk"), parsed(Some(0.5), Some("l"), "milk"));
+    }
+
+    #[test]
+    fn whole_number_glued_to_unicode_fraction() {
+        assert_eq!(parse_ingredient_line("1½ cups flour"), parsed(Some(1.5), Some("cups"), "flour"));
+    }
+
+    #[test]
+    fn decimal_quantity() {
+        assert_eq!(parse_ingredient_line("1.5 kg potatoes"), parsed(Some(1.5), Some("kg"), "potatoes"));
+    }
+
+    #[test]
+    fn unrecognized_unit_stays_part_of_name() {
+        assert_eq!(parse_ingredient_line("2 large eggs"), parsed(Some(2.0), None, "large eggs"));
+    }
+
+    #[test]
+    fn no_leading_quantity() {
+        assert_eq!(parse_ingredient_line("salt to taste"), parsed(None, None, "salt to taste"));
+    }
+
+    #[test]
+    fn glued_unit_with_no_name_falls_back_to_whole_token() {
+        assert_eq!(parse_ingredient_line("250ml"), parsed(Some(250.0), None, "ml"));
+    }
+}
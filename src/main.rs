@@ -1,5 +1,7 @@
 mod htmlscraper;
 mod database;
+mod ingredient;
+mod cache;
 mod cli;
 
 use anyhow::Result;